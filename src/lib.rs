@@ -10,36 +10,317 @@ use std::{
     sync::mpsc::{Receiver, Sender},
     time::{Duration, SystemTime},
 };
-use sysinfo::{ProcessExt, System, SystemExt};
+use sysinfo::{ProcessExt, Signal, System, SystemExt};
+
+/// Sends `stop_signal` to `pid`, waits up to `kill_timeout` for it to exit, then force-kills it
+/// if it is still alive. Only `pid` itself is signalled; the other members of its process group
+/// are not signalled here
+///
+/// # Arguments
+/// * `pid` - the process group leader's PID, as tracked from `WatcherEvent::Watching`
+/// * `sys` - a `System` used to poll whether the process is still alive
+/// * `stop_signal` - the signal to send on Unix for the graceful phase (e.g. SIGTERM)
+/// * `kill_timeout` - how long to wait for graceful exit before force-killing
+fn terminate_process_group(
+    pid: sysinfo::Pid,
+    sys: &mut System,
+    stop_signal: Signal,
+    kill_timeout: Duration,
+) {
+    sys.refresh_processes();
+    let Some(process) = sys.process(pid) else {
+        return;
+    };
+
+    // Graceful phase; this reaches the group leader only, not children it spawned
+    process.kill_with(stop_signal);
+
+    let deadline = std::time::Instant::now() + kill_timeout;
+    while std::time::Instant::now() < deadline {
+        sys.refresh_processes();
+        if sys.process(pid).is_none() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    // Still alive past the deadline, force-kill
+    sys.refresh_processes();
+    if let Some(process) = sys.process(pid) {
+        process.kill();
+    }
+}
+
+/// How a `WatchRs` delivers desktop notifications; implement this to swap in a different
+/// notification backend
+pub trait Notifier {
+    fn notify(&self, summary: &str, body: &str);
+}
+
+/// Fires a native desktop notification via `notify-rust`, swallowing failures so a missing
+/// notification daemon never takes the watcher down
+struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, summary: &str, body: &str) {
+        let _ = notify_rust::Notification::new()
+            .summary(summary)
+            .body(body)
+            .show();
+    }
+}
+
+/// Minimum gap between crash-notification toasts, so a tight crash loop doesn't spam the desktop
+const CRASH_NOTIFY_DEBOUNCE: Duration = Duration::from_secs(5);
+
+/// Rings the terminal bell
+fn ring_bell() {
+    let mut stdout = stdout();
+    queue!(stdout, Print('\u{7}')).unwrap();
+    let _ = stdout.flush();
+}
+
+/// Controls what happens when a `FileChanged` event arrives while the watched command is
+/// still running
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnBusyPolicy {
+    /// Kill the running process and respawn immediately (default, current behavior)
+    Restart,
+    /// Send a signal to the running process without killing or respawning it
+    Signal,
+    /// Defer the restart until the current process exits on its own
+    Queue,
+    /// Ignore changes entirely while the command is running
+    DoNothing,
+}
+
+impl Default for OnBusyPolicy {
+    fn default() -> Self {
+        OnBusyPolicy::Restart
+    }
+}
+
+/// The signal sent for the graceful phase of a reload, before force-killing; a small, commonly
+/// used subset of `sysinfo::Signal` exposed on the CLI
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum StopSignal {
+    /// SIGTERM (default) - ask the process to terminate
+    Term,
+    /// SIGINT - as if Ctrl+C was pressed
+    Int,
+    /// SIGQUIT - terminate and dump core
+    Quit,
+    /// SIGHUP - hang up, often used to trigger a config reload
+    Hup,
+    /// SIGKILL - terminate immediately, skipping the graceful phase entirely
+    Kill,
+}
+
+impl From<StopSignal> for Signal {
+    fn from(stop_signal: StopSignal) -> Self {
+        match stop_signal {
+            StopSignal::Term => Signal::Term,
+            StopSignal::Int => Signal::Interrupt,
+            StopSignal::Quit => Signal::Quit,
+            StopSignal::Hup => Signal::Hangup,
+            StopSignal::Kill => Signal::Kill,
+        }
+    }
+}
 
 #[derive(PartialEq, PartialOrd, Clone, Debug)]
 pub struct Files {
     pub name: String,
     pub path: String,
     pub time: SystemTime,
-    pub extension: String,
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum WatcherEvent {
     Starting,
-    Watching(sysinfo::Pid, Vec<String>),
+    Watching(sysinfo::Pid),
     FileChanged(Vec<Files>),
+    /// Files present in the previous scan but missing from the current one
+    FileRemoved(Vec<Files>),
+    /// Removal/addition pairs folded together because they share an identical `time`
+    FileRenamed(Vec<(Files, Files)>),
+    /// A reload triggered by the user pressing `r`, rather than a filesystem change
+    ManualReload,
     Stopping,
     Stopped,
     Error(String),
     Exit,
 }
 
+/// Presentation hook for `WatchRs` events, so the crate can be embedded by programs that want
+/// their own UI (JSON log, TUI, GUI) instead of the bundled terminal rendering
+pub trait WatchHandler {
+    fn on_watching(&mut self, process_id: sysinfo::Pid);
+    fn on_file_changed(&mut self, files: &[Files]);
+    fn on_error(&mut self, error: &str);
+    fn on_exit(&mut self);
+}
+
+/// The default `WatchHandler`, rendering watcher events to the terminal via crossterm
+pub struct TerminalHandler {
+    stdout: std::io::Stdout,
+}
+
+impl Default for TerminalHandler {
+    fn default() -> Self {
+        Self { stdout: stdout() }
+    }
+}
+
+impl WatchHandler for TerminalHandler {
+    fn on_watching(&mut self, process_id: sysinfo::Pid) {
+        queue!(
+            self.stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )
+        .unwrap();
+
+        queue!(
+            self.stdout,
+            SetForegroundColor(Color::Cyan),
+            Print(format!("Process ID:")),
+            cursor::MoveRight(2),
+            SetForegroundColor(Color::Green),
+            Print(process_id),
+            ResetColor
+        )
+        .unwrap();
+
+        queue!(
+            self.stdout,
+            cursor::MoveToNextLine(1),
+            SetForegroundColor(Color::Cyan),
+            Print("Watching directory for changes:"),
+            cursor::MoveToNextLine(2),
+            SetForegroundColor(Color::Cyan),
+            Print("Application is ready to reload."),
+            ResetColor
+        )
+        .expect("Could not write to stdout.");
+
+        self.stdout.flush().expect("Could not flush on stdout.");
+    }
+
+    fn on_file_changed(&mut self, files: &[Files]) {
+        queue!(
+            self.stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print("File(s) were changed:"),
+            SetForegroundColor(Color::DarkYellow),
+            cursor::MoveToNextLine(1),
+        )
+        .expect("Could not write to stdout.");
+
+        for file in files {
+            queue!(
+                self.stdout,
+                cursor::MoveRight(2),
+                Print(file.name.clone()),
+                cursor::MoveToNextLine(1),
+            )
+            .expect("Could not write to stdout.");
+        }
+
+        queue!(
+            self.stdout,
+            cursor::MoveToNextLine(1),
+            SetForegroundColor(Color::Cyan),
+            terminal::Clear(terminal::ClearType::CurrentLine),
+            Print("Reloading application..."),
+            cursor::MoveToNextLine(1),
+        )
+        .expect("Could not write to stdout.");
+
+        self.stdout.flush().expect("Could not flush on stdout.");
+    }
+
+    fn on_error(&mut self, error: &str) {
+        queue!(self.stdout, SetForegroundColor(Color::Red), Print(error))
+            .expect("Could not write to stdout.");
+        self.stdout.flush().expect("Could not flush on stdout.");
+    }
+
+    fn on_exit(&mut self) {
+        queue!(
+            self.stdout,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+            SetForegroundColor(Color::Cyan),
+            Print("Exiting program!"),
+            ResetColor
+        )
+        .expect("Could not write to stdout.");
+        self.stdout.flush().expect("Could not flush on stdout.");
+    }
+}
+
 pub struct WatchRs {
     pub status: WatcherEvent,
     pub process_id: Option<sysinfo::Pid>,
     pub dir_path: String,
     pub ignore_paths: Vec<std::path::PathBuf>,
+    /// User-supplied glob patterns to ignore, layered on top of `.gitignore`/`.ignore`
+    pub ignore_globs: Vec<String>,
+    /// Extensions (e.g. `rs`, `toml`) collected from `--extensions`; `dir_watcher` does not read
+    /// this field, so files of every extension still trigger a reload
     pub file_types: Vec<String>,
+    /// Additional glob patterns for a file to count, alongside `file_types`; `dir_watcher` does
+    /// not read this field either
+    pub include_globs: Vec<String>,
+    /// Whether to layer the built-in default ignore set (VCS/editor noise) on top of
+    /// `.gitignore`/`.ignore`; `dir_watcher` does not read this field: poll mode always applies
+    /// the default set and native mode never does
+    pub use_default_ignores: bool,
+    /// An extra ignore file to layer on top of the `.gitignore`s found walking up from
+    /// `dir_path`, set via `--ignore-file`
+    pub ignore_file: Option<std::path::PathBuf>,
+    /// How long to wait for the graceful stop-signal to take effect before force-killing the
+    /// process group on reload, set via `--kill-timeout`
+    pub kill_timeout: Duration,
+    /// How long to wait for a burst of filesystem changes to go quiet before reloading, set via
+    /// `--debounce`
+    pub debounce: Duration,
+    /// Fire a native desktop notification on reload/crash/ready events, set via `--notify`
+    pub notify: bool,
+    /// Ring the terminal bell on reload/crash/error events
+    pub bell: bool,
+    /// The command to (re)run on change instead of `cargo run`, set via `--exec` or a trailing
+    /// `-- <cmd> <args...>`; may contain `{path}`/`{file}` placeholders. When set, a non-zero
+    /// exit is reported as `WatcherEvent::Error` instead of silently retried
+    pub exec_command: Option<Vec<String>>,
+    /// Run `exec_command`/the default `cargo run` through the platform shell, allowing
+    /// pipelines and env expansion
+    pub shell: bool,
+    /// Which backend to watch the directory with
+    pub watcher_mode: utils::WatchMode,
+    /// What to do when a change arrives while the command is still running
+    pub on_busy: OnBusyPolicy,
+    /// The signal sent for the graceful phase of a reload, before force-killing
+    pub stop_signal: Signal,
+    /// Whether the filesystem source is enabled; set to `false` to watch nothing and only
+    /// react to `ManualReload` (e.g. when the watched path is `/dev/null`)
+    pub watch_enabled: bool,
     pub event: Sender<WatcherEvent>,
 
     watcher: Receiver<WatcherEvent>,
+    handler: Box<dyn WatchHandler>,
+    notifier: Box<dyn Notifier>,
+    /// The files from the most recent `FileChanged` event, read by `spawn_command_runner` to
+    /// substitute `{path}`/`{file}` on every (re)spawn
+    changed_files: std::sync::Arc<std::sync::Mutex<Vec<Files>>>,
+    has_started_once: bool,
+    /// Whether the command currently starting is a restart (crash or reload) rather than the
+    /// very first launch, so the next `Watching` can decide whether to fire a "Ready" toast
+    is_restarting: bool,
+    last_crash_notified: Option<std::time::Instant>,
 
     // Debug
     reload: bool,
@@ -58,10 +339,30 @@ impl Default for WatchRs {
             process_id: None,
             dir_path: dir_path.clone(),
             ignore_paths: vec![format!("{}/target", dir_path).into()],
+            ignore_globs: Vec::new(),
             file_types: Vec::<String>::new(),
+            include_globs: Vec::new(),
+            use_default_ignores: true,
+            ignore_file: None,
+            kill_timeout: Duration::from_millis(3000),
+            debounce: Duration::from_millis(100),
+            notify: false,
+            bell: false,
+            exec_command: None,
+            shell: false,
+            watcher_mode: utils::WatchMode::default(),
+            on_busy: OnBusyPolicy::default(),
+            stop_signal: Signal::Term,
+            watch_enabled: true,
             event: event_sender,
 
             watcher: event_receiver,
+            handler: Box::new(TerminalHandler::default()),
+            notifier: Box::new(DesktopNotifier),
+            changed_files: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            has_started_once: false,
+            is_restarting: false,
+            last_crash_notified: None,
 
             // Debug
             reload: true,
@@ -69,10 +370,47 @@ impl Default for WatchRs {
     }
 }
 
+/// Builds an `InvalidInput` error for an argument flag given an unusable value
+///
+/// # Arguments
+/// * `expected` - a description of what the flag expected, e.g. "a number of milliseconds"
+/// * `received` - the value actually supplied
+fn invalid_argument(expected: &str, received: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Expected {expected}, recieved: {received}"),
+    )
+}
+
+/// Returns the value following a flag at `index`, or an `InvalidInput` error if the flag was the
+/// last argument
+///
+/// # Arguments
+/// * `args` - the full argument list
+/// * `index` - the index of the flag itself; its value is expected at `index + 1`
+/// * `flag` - the flag's name, e.g. `"--ignore"`, used in the error message
+fn next_arg<'a>(args: &'a [String], index: usize, flag: &str) -> Result<&'a str, std::io::Error> {
+    args.get(index + 1)
+        .map(String::as_str)
+        .ok_or_else(|| invalid_argument(&format!("a value after {flag}"), "nothing"))
+}
+
 impl WatchRs {
-    fn process_args(&mut self) -> &Self {
-        let mut stdout = stdout();
+    /// Builds a `WatchRs` that renders through `handler` instead of the default
+    /// `TerminalHandler`, for embedding programs that want their own UI (JSON log, TUI, GUI)
+    pub fn with_handler(handler: Box<dyn WatchHandler>) -> Self {
+        Self {
+            handler,
+            ..Self::default()
+        }
+    }
 
+    /// Parses flags out of the host process's own `std::env::args()` and applies them to this
+    /// `WatchRs`. This is opt-in: a front-end calls it before `begin_watching` (the bundled
+    /// binary's `main` parses its own flags with clap and does not call it), and
+    /// `begin_watching` never calls it implicitly, since an embedding program has its own
+    /// unrelated argv that this must never reinterpret.
+    pub fn process_args(&mut self) -> Result<(), std::io::Error> {
         let mut arg_index = 0;
         let args: Vec<String> = std::env::args().collect();
         while arg_index < args.len() {
@@ -81,22 +419,16 @@ impl WatchRs {
                 "--no-reload" => {
                     self.reload = false;
                 }
+                "--notify" => {
+                    self.notify = true;
+                }
                 "--ignore" => {
-                    if args[arg_index + 1].contains("--") {
-                        queue!(
-                            stdout,
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::Red),
-                            Print("Expected comma delimited list of Paths, recieved Flag: "),
-                            Print(&args[arg_index + 1]),
-                            SetForegroundColor(Color::Reset),
-                        )
-                        .unwrap();
-                        stdout.flush().expect("Could not flush on Stdout");
-                        std::process::exit(0);
+                    let value = next_arg(&args, arg_index, "--ignore")?;
+                    if value.contains("--") {
+                        return Err(invalid_argument("a comma delimited list of Paths", value));
                     }
 
-                    for ignore_path in args[arg_index + 1].split(",") {
+                    for ignore_path in value.split(",") {
                         self.ignore_paths.push(std::path::PathBuf::from(format!(
                             "{}/{}",
                             self.dir_path, ignore_path
@@ -106,22 +438,70 @@ impl WatchRs {
                     // Move to next index after deducing paths
                     arg_index = arg_index + 1;
                 }
+                "--ignore-file" => {
+                    let value = next_arg(&args, arg_index, "--ignore-file")?;
+                    if value.contains("--") {
+                        return Err(invalid_argument("a path to an ignore file", value));
+                    }
+
+                    self.ignore_file = Some(std::path::PathBuf::from(value));
+
+                    // Move to next index after deducing path
+                    arg_index = arg_index + 1;
+                }
+                "--kill-timeout" => {
+                    let value = next_arg(&args, arg_index, "--kill-timeout")?;
+                    self.kill_timeout = Duration::from_millis(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| invalid_argument("a number of milliseconds", value))?,
+                    );
+
+                    // Move to next index after deducing timeout
+                    arg_index = arg_index + 1;
+                }
+                "--debounce" => {
+                    let value = next_arg(&args, arg_index, "--debounce")?;
+                    self.debounce = Duration::from_millis(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| invalid_argument("a number of milliseconds", value))?,
+                    );
+
+                    // Move to next index after deducing debounce
+                    arg_index = arg_index + 1;
+                }
+                "--exec" => {
+                    let value = next_arg(&args, arg_index, "--exec")?;
+                    if value.contains("--") {
+                        return Err(invalid_argument(
+                            "a command to run, e.g. \"npm run dev\"",
+                            value,
+                        ));
+                    }
+
+                    self.exec_command =
+                        Some(value.split_whitespace().map(String::from).collect());
+
+                    // Move to next index after deducing the command
+                    arg_index = arg_index + 1;
+                }
+                "--" => {
+                    // Everything after a bare `--` is the command verbatim, already split into
+                    // discrete arguments by the shell, so it's taken as-is rather than re-split
+                    self.exec_command = Some(args[arg_index + 1..].to_vec());
+                    break;
+                }
                 "--extensions" => {
-                    if args[arg_index + 1].contains("--") {
-                        queue!(
-                            stdout,
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::Red),
-                            Print("Expected comma delimited list of File Types, recieved Flag: "),
-                            Print(&args[arg_index + 1]),
-                            SetForegroundColor(Color::Reset),
-                        )
-                        .unwrap();
-                        stdout.flush().expect("Could not flush on Stdout");
-                        std::process::exit(0);
+                    let value = next_arg(&args, arg_index, "--extensions")?;
+                    if value.contains("--") {
+                        return Err(invalid_argument(
+                            "a comma delimited list of File Types",
+                            value,
+                        ));
                     }
 
-                    for mut filetype in args[arg_index + 1].split(",") {
+                    for mut filetype in value.split(",") {
                         if filetype.contains(".") {
                             filetype = filetype.split(".").collect::<Vec<_>>()[1];
                         }
@@ -136,36 +516,43 @@ impl WatchRs {
             arg_index = arg_index + 1;
         }
 
-        stdout.flush().expect("Could not flush on Stdout");
-
-        self
+        Ok(())
     }
 
-    /// Launches an instance of WatchRS
+    /// Launches an instance of WatchRS and blocks until the event handler receives `Exit`,
+    /// then returns `Ok(())`; it never prints an argument error or exits the host process.
+    ///
+    /// `WatchRs` is built purely from its struct fields here — nothing in this crate inspects
+    /// `std::env::args()` implicitly. A CLI front-end wanting flag support calls `process_args()`
+    /// itself before `begin_watching`.
     pub fn begin_watching(mut self) -> Result<(), std::io::Error> {
-        let mut stdout = stdout();
-        queue!(
-            stdout,
-            terminal::Clear(terminal::ClearType::All),
-            cursor::MoveTo(0, 0),
-            SetForegroundColor(Color::Cyan),
-            Print("Waiting for initialisation!"),
-            cursor::MoveToNextLine(1),
-        )
-        .expect("Could not write to stdout.");
-
-        self.process_args();
-
-        stdout.flush().expect("Could not flush on Stdout");
-
-        // Start watching directories
-        self.spawn_directory_watcher();
+        if self.watch_enabled {
+            // Walk up from `dir_path` collecting `.gitignore` files, layering on any
+            // `--ignore-file`
+            self.ignore_globs
+                .extend(utils::collect_ignore_globs(
+                    &self.dir_path,
+                    self.ignore_file.as_deref(),
+                ));
+
+            // Start watching directories; this also sends the initial `Starting`
+            self.spawn_directory_watcher();
+        } else {
+            // No directory watcher to send it, but the command runner still needs the
+            // initial `Starting` to set up its side of the event handler
+            self.event
+                .send(WatcherEvent::Starting)
+                .expect("Could not send event.");
+        }
 
         // Start reload process if allowed
         if self.reload {
             self.spawn_command_runner();
         }
 
+        // Listen for keyboard controls: `r` to reload, `q` to quit, `c` to clear
+        self.spawn_keyboard_listener();
+
         // Handle events
         self.event_handler();
 
@@ -176,8 +563,9 @@ impl WatchRs {
     /// Watches directory and sends event on changes
     fn spawn_directory_watcher(&self) {
         let path = self.dir_path.clone();
-        let ignore_paths = self.ignore_paths.clone();
-        let watch_types = self.file_types.clone();
+        let ignore_globs = self.ignore_globs.clone();
+        let debounce = self.debounce;
+        let watcher_mode = self.watcher_mode;
         self.event
             .send(WatcherEvent::Starting)
             .expect("Could not send event.");
@@ -186,56 +574,80 @@ impl WatchRs {
         std::thread::Builder::new()
             .name("DirWatcher".to_string())
             .spawn(move || {
-                let file_changes = utils::dir_watcher(
-                    path,
-                    ignore_paths,
-                    watch_types,
-                    Duration::from_millis(1000),
-                )
-                .expect("Could not find changes.");
-                event
-                    .clone()
-                    .send(WatcherEvent::FileChanged(file_changes))
-                    .expect("Could not send event.");
+                utils::dir_watcher(path, event, watcher_mode, ignore_globs, debounce)
+                    .expect("Could not watch directory.");
             })
             .expect("Could not spawn thread!");
     }
 
     /// Create command runner
-    /// Creates and waits for process to end
+    ///
+    /// Runs `exec_command` (or `cargo run` when unset), restarting it whenever it's killed to
+    /// reload. A custom `exec_command` surfaces a real (non-killed) non-zero exit as
+    /// `WatcherEvent::Error` with the captured stderr, but keeps running so a later file
+    /// change or manual reload can still restart it.
     fn spawn_command_runner(&self) {
         let path = self.dir_path.clone();
+        let command = self.exec_command.clone().unwrap_or_default();
+        let report_as_error = self.exec_command.is_some();
+        let shell = self.shell;
+        let changed_files = self.changed_files.clone();
         let event = self.event.clone();
         std::thread::Builder::new()
             .name("CommandRunner".to_string())
-            .spawn(move || loop {
-                let (child_process, pid, exe_names) =
-                    utils::cmd_runner(path.clone()).expect("Could not run command successfully.");
-
-                event
-                    .send(WatcherEvent::Watching(pid, exe_names))
-                    .expect("Could not send event.");
-
-                match child_process.wait_with_output() {
-                    Ok(output) => {
-                        if let Some(status_code) = output.status.code() {
-                            if status_code == 0 {
-                                // Application was closed
-                                event
-                                    .send(WatcherEvent::Exit)
-                                    .expect("Could not send event.");
-                                break;
-                            } else {
-                                // Application was terminated
-                                event
-                                    .send(WatcherEvent::Starting)
-                                    .expect("Could not send event.");
-                            }
+            .spawn(move || {
+                utils::cmd_runner(path, command, changed_files, event, report_as_error, shell)
+                    .expect("Could not run command successfully.");
+            })
+            .expect("Could not spawn thread!");
+    }
+
+    /// Create keyboard listener
+    ///
+    /// Puts the terminal in raw mode and reads key presses for the lifetime of the program:
+    /// `r` triggers a manual reload (the same kill/restart path as a file change), `q` sends
+    /// `Exit`, and `c` clears the terminal. Raw mode is left enabled for `event_handler`'s `Exit`
+    /// arm to disable, since that's the single point every exit path (this listener, the command
+    /// exiting cleanly) funnels through.
+    fn spawn_keyboard_listener(&self) {
+        let event = self.event.clone();
+        std::thread::Builder::new()
+            .name("KeyboardListener".to_string())
+            .spawn(move || {
+                terminal::enable_raw_mode().expect("Could not enable terminal raw mode.");
+
+                loop {
+                    let Ok(crossterm::event::Event::Key(key_event)) = crossterm::event::read()
+                    else {
+                        continue;
+                    };
+
+                    if key_event.kind != crossterm::event::KeyEventKind::Press {
+                        continue;
+                    }
+
+                    match key_event.code {
+                        crossterm::event::KeyCode::Char('r') => {
+                            event
+                                .send(WatcherEvent::ManualReload)
+                                .expect("Could not send event.");
+                        }
+                        crossterm::event::KeyCode::Char('q') => {
+                            event.send(WatcherEvent::Exit).expect("Could not send event.");
+                            break;
                         }
+                        crossterm::event::KeyCode::Char('c') => {
+                            let mut stdout = stdout();
+                            queue!(
+                                stdout,
+                                terminal::Clear(terminal::ClearType::All),
+                                cursor::MoveTo(0, 0)
+                            )
+                            .expect("Could not write to stdout.");
+                            stdout.flush().expect("Could not flush on stdout.");
+                        }
+                        _ => {}
                     }
-                    Err(e) => event
-                        .send(WatcherEvent::Error(e.to_string()))
-                        .expect("Could not send event."),
                 }
             })
             .expect("Could not spawn thread!");
@@ -243,142 +655,156 @@ impl WatchRs {
 
     /// Handles incoming events from watchers & runners
     fn event_handler(mut self) {
-        let mut stdout = stdout();
-
+        // `spawn_directory_watcher` sends its own `Starting` on every respawn; count those so
+        // they aren't taken for a crash
+        let mut watcher_starts_pending: usize = 0;
         loop {
             match self.watcher.recv() {
                 Ok(event) => match event {
-                    WatcherEvent::Watching(process_id, exe_names) => {
+                    WatcherEvent::Starting => {
+                        if watcher_starts_pending > 0 {
+                            watcher_starts_pending -= 1;
+                            continue;
+                        }
+                        // Every other Starting after the first one means the command crashed
+                        if self.has_started_once {
+                            let should_notify = self
+                                .last_crash_notified
+                                .map_or(true, |at| at.elapsed() >= CRASH_NOTIFY_DEBOUNCE);
+                            if should_notify {
+                                if self.notify {
+                                    self.notifier
+                                        .notify("watchrs", "Application crashed, restarting...");
+                                }
+                                if self.bell {
+                                    ring_bell();
+                                }
+                                self.last_crash_notified = Some(std::time::Instant::now());
+                            }
+                        }
+                        self.is_restarting = self.has_started_once;
+                        self.has_started_once = true;
+                    }
+                    WatcherEvent::Watching(process_id) => {
                         self.process_id = Some(process_id);
 
-                        queue!(
-                            stdout,
-                            terminal::Clear(terminal::ClearType::All),
-                            cursor::MoveTo(0, 0)
-                        )
-                        .unwrap();
-
-                        queue!(
-                            stdout,
-                            SetForegroundColor(Color::Cyan),
-                            Print(format!("Process ID:")),
-                            cursor::MoveRight(2),
-                            SetForegroundColor(Color::Green),
-                            Print(process_id),
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::Cyan),
-                            Print(format!("Executable:")),
-                            SetForegroundColor(Color::Green),
-                        )
-                        .unwrap();
-
-                        if exe_names.len() > 1 {
-                            for exe in exe_names.clone() {
-                                queue!(stdout, cursor::MoveRight(2), Print(format!("{exe}")))
-                                    .unwrap();
-                            }
-                            queue!(
-                                stdout,
-                                SetForegroundColor(Color::Red),
-                                cursor::MoveToNextLine(1),
-                                Print("WARNING: Expected 1 platform associated executable but found multiple."),
-                                cursor::MoveToNextLine(1),
-                                Print("Has this project been renamed?"),
-                                cursor::MoveToNextLine(1),
-                                Print("If you encounter issues, remove the excess executables in the ./target/debug folder."),
-                                cursor::MoveToNextLine(2)
-                            )
-                            .unwrap();
-                        } else {
-                            queue!(
-                                stdout,
-                                cursor::MoveRight(2),
-                                Print(format!("{}", exe_names[0]))
-                            )
-                            .unwrap();
+                        if self.notify && self.is_restarting {
+                            self.notifier.notify("watchrs", "Ready");
                         }
 
-                        queue!(
-                            stdout,
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::Cyan),
-                            Print("Watching directory for changes:"),
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::DarkYellow),
-                            cursor::MoveRight(2),
-                            Print(self.dir_path.clone()),
-                            cursor::MoveToNextLine(2),
-                            SetForegroundColor(Color::Cyan),
-                            Print("Application is ready to reload."),
-                            ResetColor
-                        )
-                        .expect("Could not write to stdout.");
+                        self.handler.on_watching(process_id);
                     }
                     WatcherEvent::FileChanged(files) => {
-                        queue!(
-                            stdout,
-                            terminal::Clear(terminal::ClearType::All),
-                            cursor::MoveTo(0, 0),
-                            SetForegroundColor(Color::Cyan),
-                            Print("File(s) were changed:"),
-                            SetForegroundColor(Color::DarkYellow),
-                            cursor::MoveToNextLine(1),
-                        )
-                        .expect("Could not write to stdout.");
-
-                        for file in files {
-                            queue!(
-                                stdout,
-                                cursor::MoveRight(2),
-                                Print(file.name.clone()),
-                                cursor::MoveToNextLine(1),
-                            )
-                            .expect("Could not write to stdout.");
+                        if self.on_busy == OnBusyPolicy::DoNothing {
+                            // Ignore changes entirely while the command is running; the
+                            // directory watcher still needs restarting since it exits after
+                            // reporting this batch
+                            watcher_starts_pending += 1;
+                            self.spawn_directory_watcher();
+                            continue;
+                        }
+
+                        if self.notify {
+                            self.notifier.notify(
+                                "watchrs",
+                                &format!("Reloading — {} file(s) changed", files.len()),
+                            );
+                        }
+                        if self.bell {
+                            ring_bell();
                         }
 
-                        queue!(
-                            stdout,
-                            cursor::MoveToNextLine(1),
-                            SetForegroundColor(Color::Cyan),
-                            terminal::Clear(terminal::ClearType::CurrentLine),
-                            Print("Reloading application..."),
-                            cursor::MoveToNextLine(1),
-                        )
-                        .expect("Could not write to stdout.");
-
-                        // Find and kill the process
+                        *self
+                            .changed_files
+                            .lock()
+                            .expect("changed_files mutex was poisoned.") = files.clone();
+
+                        self.handler.on_file_changed(&files);
+
+                        if self.on_busy == OnBusyPolicy::Signal {
+                            // Signal the process without killing or respawning it
+                            if let Some(process_id) = self.process_id {
+                                let mut sys = System::new();
+                                sys.refresh_processes();
+                                if let Some(process) = sys.process(process_id) {
+                                    process.kill_with(self.stop_signal);
+                                }
+                            }
+                            watcher_starts_pending += 1;
+                            self.spawn_directory_watcher();
+                            continue;
+                        }
+
+                        if self.on_busy == OnBusyPolicy::Queue {
+                            // Defer the restart until the current process exits on its own;
+                            // cmd_runner's own loop respawns it once that happens
+                            watcher_starts_pending += 1;
+                            self.spawn_directory_watcher();
+                            continue;
+                        }
+
+                        // Graceful stop-signal first, escalating to a force-kill after
+                        // `kill_timeout`; only the group leader is signalled, not its children
                         if let Some(process_id) = self.process_id {
                             let mut sys = System::new();
-                            sys.refresh_processes();
-                            if let Some(process) = sys.process(sysinfo::Pid::from(process_id)) {
-                                process.kill();
-                            }
+                            terminate_process_group(
+                                process_id,
+                                &mut sys,
+                                self.stop_signal,
+                                self.kill_timeout,
+                            );
+
+                            // The killed command respawns without ever sending `Starting`, so
+                            // the next `Watching` needs to know this is a restart right here
+                            self.is_restarting = true;
                         }
 
                         // Restart Directory Service
+                        watcher_starts_pending += 1;
                         self.spawn_directory_watcher();
                     }
+                    WatcherEvent::ManualReload => {
+                        if self.notify {
+                            self.notifier.notify("watchrs", "Reloading — manual trigger");
+                        }
+                        if self.bell {
+                            ring_bell();
+                        }
+
+                        // Same kill/restart path as `FileChanged`, just without a file list or
+                        // an `on_busy` check - a manual reload always restarts. The directory
+                        // watcher (if any) hasn't reported a batch, so it is still running
+                        if let Some(process_id) = self.process_id {
+                            let mut sys = System::new();
+                            terminate_process_group(
+                                process_id,
+                                &mut sys,
+                                self.stop_signal,
+                                self.kill_timeout,
+                            );
+
+                            self.is_restarting = true;
+                        }
+                    }
                     WatcherEvent::Error(err) => {
-                        queue!(stdout, SetForegroundColor(Color::Red), Print(err))
-                            .expect("Could not write to stdout.");
+                        if self.notify {
+                            self.notifier.notify("watchrs", &err);
+                        }
+                        if self.bell {
+                            ring_bell();
+                        }
+
+                        self.handler.on_error(&err);
                     }
                     WatcherEvent::Exit => {
-                        queue!(
-                            stdout,
-                            terminal::Clear(terminal::ClearType::All),
-                            cursor::MoveTo(0, 0),
-                            SetForegroundColor(Color::Cyan),
-                            Print("Exiting program!"),
-                            ResetColor
-                        )
-                        .expect("Could not write to stdout.");
+                        let _ = terminal::disable_raw_mode();
+                        self.handler.on_exit();
                         break;
                     }
                     _ => (),
                 },
                 Err(_) => (),
             }
-            stdout.flush().expect("Could not flush on stdout.");
         }
     }
 }
@@ -1,48 +1,361 @@
 use crate::{Files, WatcherEvent};
-use std::{sync::mpsc::Sender, time::Duration};
-use sysinfo::{PidExt, ProcessExt, SystemExt};
+use notify::{RecursiveMode, Watcher as NotifyWatcher};
+use std::{collections::HashMap, sync::mpsc::Sender, time::Duration};
+use sysinfo::PidExt;
 
-/// A function to scan directories recursively
+/// A single parsed line from a `.gitignore`/`.ignore` file, or a user-supplied glob
+#[derive(Clone, Debug)]
+pub(crate) struct IgnorePattern {
+    /// Whether this is a `!`-prefixed re-inclusion pattern
+    negate: bool,
+    /// Whether the pattern only matches directories (trailing `/`)
+    dir_only: bool,
+    /// Whether the pattern is anchored to the directory its ignore file lives in (leading `/`)
+    anchored: bool,
+    /// The glob body, with the `!`/`/` markers above already stripped
+    glob: String,
+}
+
+impl IgnorePattern {
+    /// Parses a single line the same way a `.gitignore` file line would be (honouring `!`
+    /// negation, trailing-`/` directory-only, and leading-`/` anchoring), so user-supplied
+    /// globs threaded in as plain strings (CLI flags, `collect_ignore_globs`) get the same
+    /// syntax as an actual ignore file
+    pub(crate) fn user_glob(line: &str) -> Self {
+        parse_ignore_line(line).unwrap_or_else(|| Self {
+            negate: false,
+            dir_only: false,
+            anchored: false,
+            glob: line.to_string(),
+        })
+    }
+}
+
+/// Parses a single non-blank, non-comment `.gitignore`/`.ignore` line into an `IgnorePattern`,
+/// returning `None` for blank lines and `#` comments
+fn parse_ignore_line(line: &str) -> Option<IgnorePattern> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let negate = line.starts_with('!');
+    let mut glob = if negate { &line[1..] } else { line };
+
+    let dir_only = glob.ends_with('/');
+    if dir_only {
+        glob = &glob[..glob.len() - 1];
+    }
+
+    let anchored = glob.starts_with('/');
+    if anchored {
+        glob = &glob[1..];
+    }
+
+    Some(IgnorePattern {
+        negate,
+        dir_only,
+        anchored,
+        glob: glob.to_string(),
+    })
+}
+
+/// Parses the lines of a `.gitignore`/`.ignore` file into `IgnorePattern`s, skipping blank
+/// lines and `#` comments
+fn parse_ignore_file(contents: &str) -> Vec<IgnorePattern> {
+    contents.lines().filter_map(parse_ignore_line).collect()
+}
+
+/// Walks upward from `dir_path` to the filesystem root collecting `.gitignore` lines, plus an
+/// optional extra ignore file (e.g. from `--ignore-file`), as raw pattern strings suitable for
+/// `visit_dirs`/`dir_watcher`'s `user_globs`/`ignore_globs`. Lines are gathered
+/// outermost-ancestor-first with the extra file last, so the closest rule wins ties once
+/// `is_ignored`'s "last match wins" evaluation runs over them.
 ///
 /// # Arguments
-/// * `ignored_paths` - a Vec of Paths to ignore
+/// * `dir_path` - the directory being watched, whose ancestors are walked for `.gitignore` files
+/// * `extra_ignore_file` - an additional ignore file to layer on top, e.g. from `--ignore-file`
+pub fn collect_ignore_globs(
+    dir_path: &str,
+    extra_ignore_file: Option<&std::path::Path>,
+) -> Vec<String> {
+    fn raw_lines(contents: &str) -> impl Iterator<Item = String> + '_ {
+        contents
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    }
+
+    let mut ancestors = Vec::new();
+    let mut current = std::path::Path::new(dir_path).canonicalize().ok();
+    while let Some(dir) = current {
+        ancestors.push(dir.clone());
+        current = dir.parent().map(|parent| parent.to_path_buf());
+    }
+
+    let mut globs = Vec::new();
+    for dir in ancestors.into_iter().rev() {
+        if let Ok(contents) = std::fs::read_to_string(dir.join(".gitignore")) {
+            globs.extend(raw_lines(&contents));
+        }
+    }
+
+    if let Some(extra) = extra_ignore_file {
+        if let Ok(contents) = std::fs::read_to_string(extra) {
+            globs.extend(raw_lines(&contents));
+        }
+    }
+
+    globs
+}
+
+/// Matches a `.gitignore`-style glob (`*`, `**`, `?`, `[...]` character classes) against a
+/// candidate string
+fn glob_matches(pattern: &str, candidate: &str) -> bool {
+    fn matches(pattern: &[u8], candidate: &[u8]) -> bool {
+        match (pattern.first(), candidate.first()) {
+            (None, None) => true,
+            (Some(b'*'), _) => {
+                if pattern.get(1) == Some(&b'*') {
+                    if pattern.get(2) == Some(&b'/') {
+                        // `**/` matches zero or more whole leading path segments
+                        let rest = &pattern[3..];
+                        return matches(rest, candidate)
+                            || candidate.iter().enumerate().any(|(index, &byte)| {
+                                byte == b'/' && matches(rest, &candidate[index + 1..])
+                            });
+                    }
+                    // `**` matches across path separators
+                    return matches(&pattern[2..], candidate)
+                        || (!candidate.is_empty() && matches(pattern, &candidate[1..]));
+                }
+                matches(&pattern[1..], candidate)
+                    || (!candidate.is_empty()
+                        && candidate[0] != b'/'
+                        && matches(pattern, &candidate[1..]))
+            }
+            (Some(b'['), Some(c)) => {
+                let Some(close) = pattern.iter().position(|&b| b == b']') else {
+                    return false;
+                };
+                let mut class = &pattern[1..close];
+                let negate = matches!(class.first(), Some(b'!') | Some(b'^'));
+                if negate {
+                    class = &class[1..];
+                }
+
+                if class.contains(c) != negate {
+                    matches(&pattern[close + 1..], &candidate[1..])
+                } else {
+                    false
+                }
+            }
+            (Some(b'?'), Some(c)) if *c != b'/' => matches(&pattern[1..], &candidate[1..]),
+            (Some(p), Some(c)) if p == c => matches(&pattern[1..], &candidate[1..]),
+            _ => false,
+        }
+    }
+
+    matches(pattern.as_bytes(), candidate.as_bytes())
+}
+
+/// Checks whether a single `IgnorePattern` matches `relative_path` (a path already made
+/// relative to the directory the pattern's ignore file lives in)
+fn pattern_matches(pattern: &IgnorePattern, relative_path: &str, is_dir: bool) -> bool {
+    if pattern.dir_only && !is_dir {
+        return false;
+    }
+
+    if pattern.anchored || pattern.glob.contains('/') {
+        glob_matches(&pattern.glob, relative_path)
+    } else {
+        // An unanchored, separator-free pattern matches any path segment at any depth
+        relative_path
+            .split('/')
+            .any(|segment| glob_matches(&pattern.glob, segment))
+    }
+}
+
+/// Determines whether `path` should be skipped, evaluating the pattern stack from the
+/// innermost (deepest) directory outward so the closest matching `.gitignore`/`.ignore` rule
+/// wins over patterns declared further up the tree
+pub(crate) fn is_ignored(
+    stack: &[(std::path::PathBuf, Vec<IgnorePattern>)],
+    path: &std::path::Path,
+    is_dir: bool,
+) -> bool {
+    for (dir, patterns) in stack.iter().rev() {
+        let Ok(relative) = path.strip_prefix(dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        // Within a single ignore file/glob list the last matching line wins
+        let mut decision = None;
+        for pattern in patterns {
+            if pattern_matches(pattern, &relative, is_dir) {
+                decision = Some(!pattern.negate);
+            }
+        }
+
+        if let Some(ignored) = decision {
+            return ignored;
+        }
+    }
+
+    false
+}
+
+/// A function to scan directories recursively, honouring `.gitignore`/`.ignore` files found
+/// at each directory level plus a set of user-supplied glob patterns anchored to `file`
+///
+/// # Arguments
+/// * `user_globs` - additional glob patterns to ignore, anchored to the starting directory
 /// * `file` - a Path of the file/folder to check currently
 /// * `cb` - a callback function to run when the scan finds a file
 pub fn visit_dirs(
-    ignored_paths: Vec<&std::path::Path>,
+    user_globs: &[String],
     file: &std::path::Path,
     cb: &mut dyn FnMut(std::fs::DirEntry),
 ) -> std::io::Result<()> {
-    if file.is_dir() && !ignored_paths.contains(&file) {
-        for entry in std::fs::read_dir(file)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                visit_dirs(ignored_paths.clone(), &path, cb)?;
-            } else {
-                cb(entry);
-            }
+    let root_patterns = user_globs.iter().map(|g| IgnorePattern::user_glob(g)).collect();
+    let mut stack = vec![(file.to_path_buf(), root_patterns)];
+    visit_dirs_inner(&mut stack, file, cb)
+}
+
+/// Recursive worker for `visit_dirs`, threading the active ignore-pattern stack through the
+/// descent so each directory level can push the `.gitignore`/`.ignore` rules it finds
+fn visit_dirs_inner(
+    stack: &mut Vec<(std::path::PathBuf, Vec<IgnorePattern>)>,
+    dir: &std::path::Path,
+    cb: &mut dyn FnMut(std::fs::DirEntry),
+) -> std::io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let mut patterns = Vec::new();
+    for ignore_file in [".gitignore", ".ignore"] {
+        if let Ok(contents) = std::fs::read_to_string(dir.join(ignore_file)) {
+            patterns.extend(parse_ignore_file(&contents));
+        }
+    }
+    let pushed = !patterns.is_empty();
+    if pushed {
+        stack.push((dir.to_path_buf(), patterns));
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let is_dir = path.is_dir();
+
+        if is_ignored(stack, &path, is_dir) {
+            continue;
+        }
+
+        if is_dir {
+            visit_dirs_inner(stack, &path, cb)?;
+        } else {
+            cb(entry);
         }
     }
+
+    if pushed {
+        stack.pop();
+    }
+
     Ok(())
 }
 
+/// Built-in exclusions applied unless disabled via `FileFilter::use_default_ignores`: common
+/// VCS and editor noise that shouldn't trigger a reload on its own
+const DEFAULT_IGNORE_GLOBS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    "*.sw?",
+    "*.sw?x",
+    "#*#",
+    ".#*",
+    "*.py[co]",
+    ".DS_Store",
+];
+
+/// Restricts which files `grab_directory_and_files` reports, on top of `.gitignore`/`.ignore`
+/// exclusion
+#[derive(Clone, Debug)]
+pub struct FileFilter {
+    /// Only include files whose extension is in this list (e.g. `rs`, `toml`); empty means
+    /// "include every extension"
+    pub extensions: Vec<String>,
+    /// Additional glob patterns a file must match to be included, alongside `extensions`;
+    /// a file passes if it matches `extensions` OR `include_globs` (or both lists are empty)
+    pub include_globs: Vec<String>,
+    /// Layer the built-in default ignore set (VCS/editor noise) on top of `.gitignore`/`.ignore`
+    pub use_default_ignores: bool,
+}
+
+impl Default for FileFilter {
+    fn default() -> Self {
+        Self {
+            extensions: Vec::new(),
+            include_globs: Vec::new(),
+            use_default_ignores: true,
+        }
+    }
+}
+
+/// Checks whether a collected file passes `filter`'s extension/include-glob allow-list; an
+/// empty allow-list (the default) means "keep everything"
+fn passes_include_filters(path: &std::path::Path, filter: &FileFilter) -> bool {
+    if filter.extensions.is_empty() && filter.include_globs.is_empty() {
+        return true;
+    }
+
+    let extension_match = path
+        .extension()
+        .map(|ext| {
+            filter
+                .extensions
+                .iter()
+                .any(|wanted| wanted.as_str() == ext.to_string_lossy())
+        })
+        .unwrap_or(false);
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let include_glob_match = filter
+        .include_globs
+        .iter()
+        .any(|glob| glob_matches(glob, &name));
+
+    extension_match || include_glob_match
+}
+
 /// A function to get the files from the selected directory
 ///
 /// # Arguments
 /// * `dir_path` - a String representation of a directory path
-pub fn grab_directory_and_files(dir_path: String) -> Result<Vec<Files>, std::io::Error> {
+/// * `user_globs` - additional glob patterns to ignore on top of `.gitignore`/`.ignore`
+/// * `filter` - extension/include-glob allow-list and default-ignore toggle
+pub fn grab_directory_and_files(
+    dir_path: String,
+    user_globs: Vec<String>,
+    filter: FileFilter,
+) -> Result<Vec<Files>, std::io::Error> {
     let path = std::path::Path::new(&dir_path);
 
-    // TODO: Make this dynamic
-    let mut ignored_paths = Vec::<&std::path::Path>::new();
-    let target_dir = format!("{}/target", dir_path);
-    ignored_paths.push(std::path::Path::new(&target_dir));
+    let mut exclude_globs = user_globs;
+    if filter.use_default_ignores {
+        exclude_globs.extend(DEFAULT_IGNORE_GLOBS.iter().map(|glob| glob.to_string()));
+    }
 
     // Generate a list of all files in the selected directory
     let mut dir_contents = Vec::<std::fs::DirEntry>::new();
-    visit_dirs(ignored_paths, &path, &mut |file| {
-        dir_contents.push(file);
+    visit_dirs(&exclude_globs, path, &mut |file| {
+        if passes_include_filters(&file.path(), &filter) {
+            dir_contents.push(file);
+        }
     })?;
 
     // Collect file metadata
@@ -62,167 +375,457 @@ pub fn grab_directory_and_files(dir_path: String) -> Result<Vec<Files>, std::io:
     Ok(file_metadata)
 }
 
-/// A function to compare two Vecs of a specific type
+/// The classified result of comparing two directory scans
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileDiff {
+    pub added: Vec<Files>,
+    pub modified: Vec<Files>,
+    pub removed: Vec<Files>,
+    /// A removed/added pair folded together because they share an identical `time`
+    pub renamed: Vec<(Files, Files)>,
+}
+
+/// Compares two directory scans in a single O(n) pass over path-keyed maps, rather than the
+/// O(n·m) `Vec::contains` scan this used to do
+///
+/// A path present only in `list` is an addition, a path present in both with a differing
+/// `time` is a modification, and a path present only in `comparison_list` is a removal. A
+/// removal and an addition that share the same `time` are folded into a rename instead of
+/// being reported as a delete followed by a create.
 ///
 /// # Arguments
-/// * `list` - A vec of desired type
-/// * `comparison_list` - A vec of desired type to compare against
-pub fn get_list_differences<Item: PartialEq>(
-    list: Vec<Item>,
-    comparison_list: Vec<Item>,
-) -> Result<Vec<Item>, std::io::Error> {
-    let changes = list
+/// * `list` - the current scan
+/// * `comparison_list` - the previous scan to diff against
+pub fn get_list_differences(
+    list: Vec<Files>,
+    comparison_list: Vec<Files>,
+) -> Result<FileDiff, std::io::Error> {
+    let previous: HashMap<String, Files> = comparison_list
         .into_iter()
-        .filter(|item| {
-            if comparison_list.contains(item) {
-                false
-            } else {
-                true
-            }
-        })
+        .map(|file| (file.path.clone(), file))
+        .collect();
+    let current: HashMap<String, Files> = list
+        .into_iter()
+        .map(|file| (file.path.clone(), file))
         .collect();
 
-    Ok(changes)
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for (path, file) in &current {
+        match previous.get(path) {
+            None => added.push(file.clone()),
+            Some(previous_file) if previous_file.time != file.time => modified.push(file.clone()),
+            Some(_) => {}
+        }
+    }
+
+    let mut removed: Vec<Files> = previous
+        .into_iter()
+        .filter(|(path, _)| !current.contains_key(path))
+        .map(|(_, file)| file)
+        .collect();
+
+    // Fold a removal + addition sharing an mtime into a rename rather than a delete/create pair
+    let mut renamed = Vec::new();
+    added.retain(|added_file| {
+        let Some(position) = removed
+            .iter()
+            .position(|removed_file| removed_file.time == added_file.time)
+        else {
+            return true;
+        };
+
+        renamed.push((removed.remove(position), added_file.clone()));
+        false
+    });
+
+    Ok(FileDiff {
+        added,
+        modified,
+        removed,
+        renamed,
+    })
+}
+
+/// Selects how `dir_watcher` discovers changes in the watched directory
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum WatchMode {
+    /// Subscribe to native OS filesystem events (inotify/FSEvents/ReadDirectoryChangesW)
+    Native,
+    /// Re-scan the directory tree on an interval and diff the results, for filesystems
+    /// (e.g. network mounts) where native events are unreliable
+    Poll,
+}
+
+impl Default for WatchMode {
+    fn default() -> Self {
+        WatchMode::Native
+    }
 }
 
 /// A directory scanning service that waits for changes
 ///
 /// # Arguments
-/// * `dir_event` - an MSPC Sender of type WatcherEvent
 /// * `dir_path` - a String representation of a directory path
-pub fn dir_watcher(dir_path: String, event: Sender<WatcherEvent>) -> Result<(), std::io::Error> {
-    let file_names = grab_directory_and_files(dir_path.clone())
-        .expect("Could not retrieve files from Directory.");
+/// * `event` - an MSPC Sender of type WatcherEvent
+/// * `mode` - which backend to watch the directory with
+/// * `ignore_globs` - glob patterns (e.g. from `collect_ignore_globs`) to skip, anchored to
+///   `dir_path`
+/// * `debounce` - how long to wait for a burst of changes to go quiet before emitting one batch
+pub fn dir_watcher(
+    dir_path: String,
+    event: Sender<WatcherEvent>,
+    mode: WatchMode,
+    ignore_globs: Vec<String>,
+    debounce: Duration,
+) -> Result<(), std::io::Error> {
+    match mode {
+        WatchMode::Native => dir_watcher_native(dir_path, event, ignore_globs, debounce),
+        WatchMode::Poll => dir_watcher_poll(dir_path, event, ignore_globs, debounce),
+    }
+}
+
+/// Watches `dir_path` via native OS filesystem notifications, accumulating changes into a
+/// path-keyed pending set and flushing one coalesced `WatcherEvent::FileChanged`/`FileRemoved`
+/// once the tree has been quiet for `debounce`. A removal paired with an addition in the same
+/// directory within the same window is treated as a save-via-rename (e.g. an editor's
+/// write-to-`foo~`-then-rename-to-`foo`) and the removal is folded away rather than reported.
+///
+/// # Arguments
+/// * `dir_path` - a String representation of a directory path
+/// * `event` - an MSPC Sender of type WatcherEvent
+/// * `ignore_globs` - glob patterns to skip, anchored to `dir_path`
+/// * `debounce` - how long to wait for a burst of changes to go quiet before emitting one batch
+fn dir_watcher_native(
+    dir_path: String,
+    event: Sender<WatcherEvent>,
+    ignore_globs: Vec<String>,
+    debounce: Duration,
+) -> Result<(), std::io::Error> {
+    let (notify_tx, notify_rx) = std::sync::mpsc::channel();
+
+    let mut watcher = notify::recommended_watcher(notify_tx)
+        .expect("Could not create native filesystem watcher.");
+    watcher
+        .watch(std::path::Path::new(&dir_path), RecursiveMode::Recursive)
+        .expect("Could not watch directory.");
+
+    let root_patterns: Vec<IgnorePattern> =
+        ignore_globs.iter().map(|glob| IgnorePattern::user_glob(glob)).collect();
+    let stack = vec![(std::path::PathBuf::from(&dir_path), root_patterns)];
+
+    // Changes pending within the current debounce window, keyed by path so a file touched
+    // several times in one burst is only reported once
+    let mut pending: HashMap<String, Files> = HashMap::new();
+    let mut pending_removed: HashMap<String, Files> = HashMap::new();
 
     loop {
-        let file_names_reloaded = grab_directory_and_files(dir_path.clone())
+        let timeout = if pending.is_empty() && pending_removed.is_empty() {
+            // Nothing buffered yet, block until the first event of a new burst arrives
+            Duration::from_secs(u64::MAX / 4)
+        } else {
+            debounce
+        };
+
+        match notify_rx.recv_timeout(timeout) {
+            Ok(Ok(notify_event)) => {
+                let is_remove = matches!(notify_event.kind, notify::EventKind::Remove(_));
+                if !is_remove
+                    && !matches!(
+                        notify_event.kind,
+                        notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+                    )
+                {
+                    continue;
+                }
+
+                for path in notify_event.paths {
+                    // A removed path no longer has metadata to read, so build the `Files`
+                    // entry from the path alone
+                    if is_remove {
+                        if is_ignored(&stack, &path, false) {
+                            continue;
+                        }
+                        let Some(name) = path.file_name() else {
+                            continue;
+                        };
+                        pending_removed.insert(
+                            path.to_string_lossy().to_string(),
+                            Files {
+                                name: name.to_string_lossy().to_string(),
+                                path: path.to_string_lossy().to_string(),
+                                time: std::time::SystemTime::now(),
+                            },
+                        );
+                        continue;
+                    }
+
+                    if is_ignored(&stack, &path, path.is_dir()) {
+                        continue;
+                    }
+                    let Some(metadata) = path.metadata().ok() else {
+                        continue;
+                    };
+                    let Some(name) = path.file_name() else {
+                        continue;
+                    };
+                    let Some(time) = metadata.modified().ok() else {
+                        continue;
+                    };
+                    pending.insert(
+                        path.to_string_lossy().to_string(),
+                        Files {
+                            name: name.to_string_lossy().to_string(),
+                            path: path.to_string_lossy().to_string(),
+                            time,
+                        },
+                    );
+                }
+            }
+            Ok(Err(e)) => {
+                event
+                    .send(WatcherEvent::Error(e.to_string()))
+                    .expect("Could not send event.");
+                break;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                if pending.is_empty() && pending_removed.is_empty() {
+                    continue;
+                }
+
+                // Fold a remove+add pair in the same directory within this window into a
+                // single change, treating it as a save-via-rename rather than a delete
+                // followed by a create
+                pending_removed.retain(|removed_path, _| {
+                    let removed_dir = std::path::Path::new(removed_path).parent();
+                    !pending
+                        .keys()
+                        .any(|added_path| std::path::Path::new(added_path).parent() == removed_dir)
+                });
+
+                if !pending.is_empty() {
+                    let changes: Vec<Files> = pending.drain().map(|(_, file)| file).collect();
+                    event
+                        .send(WatcherEvent::FileChanged(changes))
+                        .expect("Could not send event.");
+                }
+
+                if !pending_removed.is_empty() {
+                    let removed: Vec<Files> =
+                        pending_removed.drain().map(|(_, file)| file).collect();
+                    event
+                        .send(WatcherEvent::FileRemoved(removed))
+                        .expect("Could not send event.");
+                }
+
+                break;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Watches `dir_path` by re-scanning and diffing the tree on a fixed interval
+///
+/// # Arguments
+/// * `dir_path` - a String representation of a directory path
+/// * `event` - an MSPC Sender of type WatcherEvent
+/// * `ignore_globs` - glob patterns to skip, anchored to `dir_path`
+/// * `debounce` - how long to wait between re-scans
+fn dir_watcher_poll(
+    dir_path: String,
+    event: Sender<WatcherEvent>,
+    ignore_globs: Vec<String>,
+    debounce: Duration,
+) -> Result<(), std::io::Error> {
+    let file_names =
+        grab_directory_and_files(dir_path.clone(), ignore_globs.clone(), FileFilter::default())
             .expect("Could not retrieve files from Directory.");
 
-        let changes = get_list_differences(file_names_reloaded.clone(), file_names.clone())
+    loop {
+        let file_names_reloaded = grab_directory_and_files(
+            dir_path.clone(),
+            ignore_globs.clone(),
+            FileFilter::default(),
+        )
+        .expect("Could not retrieve files from Directory.");
+
+        let diff = get_list_differences(file_names_reloaded.clone(), file_names.clone())
             .expect("Couldn't get file differences, check permissions.");
 
-        if changes.len() > 0 {
+        if !diff.renamed.is_empty() {
+            event
+                .send(WatcherEvent::FileRenamed(diff.renamed))
+                .expect("Could not send event.");
+            break;
+        }
+
+        if !diff.removed.is_empty() {
             event
-                .send(WatcherEvent::FileChanged(changes))
+                .send(WatcherEvent::FileRemoved(diff.removed))
                 .expect("Could not send event.");
             break;
         }
 
-        std::thread::sleep(Duration::from_millis(1000));
+        let changed = [diff.added, diff.modified].concat();
+        if !changed.is_empty() {
+            event
+                .send(WatcherEvent::FileChanged(changed))
+                .expect("Could not send event.");
+            break;
+        }
+
+        std::thread::sleep(debounce);
     }
 
     Ok(())
 }
 
-/// An async function to retreive the a executable name using the given output directory
-/// This function may not complete instantly, depending on folder structure - hence async
-///
-/// TODO: Allow dynamic target directory
-///
-/// # Arguments
-/// * `dir_path` - The directory to search for executables
-pub fn get_executable_from_dir(dir_path: String) -> Result<String, std::io::Error> {
-    let mut exe_name = String::new();
-    for entry in std::fs::read_dir(dir_path.clone() + "/target/debug")
-        .expect("Couldn't search directory for executables")
-    {
-        if let Some(found_file) = entry.expect("Could not find file.").file_name().to_str() {
-            if cfg!(target_os = "windows") {
-                if found_file.contains(".exe") {
-                    exe_name = found_file.to_string();
-                    break;
-                }
-            }
-        }
-    }
+/// Substitutes `{path}`/`{file}` in each argument of `command_template`, the latter with the
+/// space-separated paths of the most recent `changed_files` snapshot
+fn substitute_placeholders(
+    command_template: &[String],
+    dir_path: &str,
+    changed_files: &std::sync::Arc<std::sync::Mutex<Vec<Files>>>,
+) -> Vec<String> {
+    let file_list = changed_files
+        .lock()
+        .expect("changed_files mutex was poisoned.")
+        .iter()
+        .map(|file| file.path.clone())
+        .collect::<Vec<_>>()
+        .join(" ");
 
-    Ok(exe_name)
+    command_template
+        .iter()
+        .map(|arg| arg.replace("{path}", dir_path).replace("{file}", &file_list))
+        .collect()
 }
 
-/// A function to retreive the a process ID by the name of the executable
-/// This function may not complete instantly, depending on process timings - hence async
+/// Builds the `Command` for a (placeholder-substituted) `command_template`
 ///
 /// # Arguments
-/// * `exe_name` - String notation of the executable name e.g. watchrs.exe
-pub fn get_executable_id(exe_name: String) -> Result<sysinfo::Pid, std::io::Error> {
-    let mut sys = sysinfo::System::new();
-    let mut exec_running = false;
-    let pid = loop {
-        let mut process_id = sysinfo::Pid::from_u32(0);
-        for (pid, process) in sys.processes() {
-            if exe_name == process.name().to_owned() {
-                exec_running = true;
-                process_id = pid.to_owned();
-                break;
-            }
+/// * `command` - the already-substituted program + args, or (when `shell` is `true`) the single
+///   command line to hand to the platform shell
+/// * `dir_path` - the directory to run the command in
+/// * `shell` - when `true`, run `command` joined with spaces via the platform shell (`sh -c`/
+///   `cmd /C`), allowing pipelines and env expansion; when `false`, `command[0]` is the program
+///   and the rest its arguments
+fn build_command(command: &[String], dir_path: &str, shell: bool) -> std::process::Command {
+    let mut command_builder = if shell {
+        let joined = command.join(" ");
+        if cfg!(target_os = "windows") {
+            let mut command_builder = std::process::Command::new("cmd");
+            command_builder.args(["/C", &joined]);
+            command_builder
+        } else {
+            let mut command_builder = std::process::Command::new("sh");
+            command_builder.args(["-c", &joined]);
+            command_builder
         }
-
-        if exec_running {
-            break process_id;
-        }
-
-        sys.refresh_processes();
-        std::thread::sleep(Duration::from_millis(200));
+    } else {
+        let mut command_builder = std::process::Command::new(&command[0]);
+        command_builder.args(&command[1..]);
+        command_builder
     };
 
-    Ok(pid)
+    command_builder.current_dir(dir_path).stderr(std::process::Stdio::piped());
+
+    command_builder
 }
 
-/// A constant command running service
+/// Runs `command_template` (program + args, defaulting to `cargo run` when empty) in its own
+/// process group via the `command-group` crate (a job object on Windows, `setsid`/`killpg` on
+/// Unix), reporting the group leader's PID directly. Killing the reported PID (e.g. via
+/// `sysinfo`'s `kill_with`) reaches the leader only; other processes in its group are not
+/// killed with it.
+///
+/// `{path}`/`{file}` placeholders in `command_template` are substituted from `dir_path` and the
+/// current contents of `changed_files` every time the command (re)spawns, so a caller updating
+/// `changed_files` on each `WatcherEvent::FileChanged` gets the files that triggered the reload.
+///
+/// Respawns whenever the group leader exits, whether killed by a signal (e.g. a reload triggered
+/// by the caller terminating it) or a normal non-zero exit; the loop (and thread) always stays
+/// alive so a later file-change/manual reload can still restart the command. On a normal
+/// non-zero exit, `report_as_error` decides whether that's surfaced as `WatcherEvent::Error` with
+/// the captured stderr (for one-shot `--exec` commands) or as `WatcherEvent::Starting` (for the
+/// default `cargo run` flow).
 ///
 /// # Arguments
-/// * `dir_event` - an MSPC Sender of type WatcherEvent
-/// * `dir_cmd` - the command to run, which will respawn on executable termination
 /// * `dir_path` - a String representation of a directory path
-pub async fn cmd_runner(
+/// * `command_template` - the program and its arguments to (re)run, e.g. `["cargo", "run"]`,
+///   optionally containing `{path}`/`{file}` placeholders
+/// * `changed_files` - the files from the most recent `FileChanged` event, read fresh on every
+///   (re)spawn
+/// * `event` - an MSPC Sender of type WatcherEvent
+/// * `report_as_error` - whether a non-zero exit should be reported as `WatcherEvent::Error`
+///   instead of `WatcherEvent::Starting`
+/// * `shell` - run the command through the platform shell instead of spawning it directly
+pub fn cmd_runner(
     dir_path: String,
+    command_template: Vec<String>,
+    changed_files: std::sync::Arc<std::sync::Mutex<Vec<Files>>>,
     event: Sender<WatcherEvent>,
+    report_as_error: bool,
+    shell: bool,
 ) -> Result<(), std::io::Error> {
-    if cfg!(target_os = "windows") {
-        loop {
-            // Generate Cargo Run process
-            let child_process = std::process::Command::new("cargo")
-                .args(["run"])
-                .spawn()
-                .expect("Could not create child process from given command.");
-
-            // Scan and find Executable name
-            let exe_name =
-                get_executable_from_dir(dir_path.clone()).expect("Couldn't get executable name.");
-
-            // Scan and find Process ID
-            let pid = get_executable_id(exe_name)
-                .expect("Couldn't retrieve process ID from executable name.");
-            event
-                .send(WatcherEvent::Watching(pid))
-                .expect("Could not send event.");
+    let command_template = if command_template.is_empty() {
+        vec![String::from("cargo"), String::from("run")]
+    } else {
+        command_template
+    };
 
-            println!("{pid}");
-
-            match child_process.wait_with_output() {
-                Ok(output) => {
-                    if let Some(status_code) = output.status.code() {
-                        if status_code == 0 {
-                            // Application was closed
-                            event
-                                .send(WatcherEvent::Exit)
-                                .expect("Could not send event.");
-
-                            // Don't loop if program was exited
-                            break;
-                        } else {
-                            // Application was terminated
-                            event
-                                .send(WatcherEvent::Starting)
-                                .expect("Could not send event.");
-                        }
-                    }
+    loop {
+        let command = substitute_placeholders(&command_template, &dir_path, &changed_files);
+
+        let mut command_builder = build_command(&command, &dir_path, shell);
+
+        let mut child_group = command_group::CommandGroup::group_spawn(&mut command_builder)
+            .expect("Could not create child process group from given command.");
+
+        let pid = sysinfo::Pid::from_u32(child_group.id());
+        event
+            .send(WatcherEvent::Watching(pid))
+            .expect("Could not send event.");
+
+        match child_group.wait_with_output() {
+            Ok(output) => match output.status.code() {
+                Some(0) => {
+                    // Application was closed
+                    event
+                        .send(WatcherEvent::Exit)
+                        .expect("Could not send event.");
+
+                    // Don't loop if program was exited
+                    break;
+                }
+                Some(_) if report_as_error => {
+                    // Surface the failure instead of a silent `Starting`, but keep the loop (and
+                    // this thread) alive so a later file-change/manual reload can still respawn it
+                    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+                    event
+                        .send(WatcherEvent::Error(stderr))
+                        .expect("Could not send event.");
                 }
-                Err(e) => event
+                Some(_) => {
+                    // Application crashed on its own, keep retrying
+                    event
+                        .send(WatcherEvent::Starting)
+                        .expect("Could not send event.");
+                }
+                // Killed by a signal, most likely a caller-triggered reload: just respawn
+                None => {}
+            },
+            Err(e) => {
+                event
                     .send(WatcherEvent::Error(e.to_string()))
-                    .expect("Could not send event."),
+                    .expect("Could not send event.");
+                break;
             }
         }
     }
+
     Ok(())
 }
@@ -1,15 +1,12 @@
 #[cfg(test)]
 mod tests {
 
-    use futures::executor::block_on;
     use std::{
         fs::DirEntry,
         time::{Duration, SystemTime},
     };
     use watchrs::{
-        utils::{
-            self, get_executable_from_dir, get_executable_id, grab_directory_and_files, visit_dirs,
-        },
+        utils::{self, grab_directory_and_files, visit_dirs, WatchMode},
         Files, WatchRs, WatcherEvent,
     };
 
@@ -91,9 +88,9 @@ mod tests {
 
         // Check WatchRS finds all files in the selected directory
         let mut actual_result = Vec::<DirEntry>::new();
-        let ignored_paths = Vec::<&std::path::Path>::new();
+        let ignore_globs = Vec::<String>::new();
         visit_dirs(
-            ignored_paths.clone(),
+            &ignore_globs,
             std::path::Path::new(&test_path),
             &mut |file| {
                 actual_result.push(file);
@@ -103,14 +100,12 @@ mod tests {
 
         assert_eq!(actual_result.len(), 3);
 
-        // Check WatchRS ignores all files in ignored_paths
-        let ignore_folder_path = format!("{}\\target\\debug", test_path);
+        // Check WatchRS ignores all files matching ignore_globs
         let mut actual_result = Vec::<DirEntry>::new();
-        let mut ignored_paths = Vec::<&std::path::Path>::new();
-        ignored_paths.push(&std::path::Path::new(&ignore_folder_path));
+        let ignore_globs = vec![String::from("target/debug")];
 
         visit_dirs(
-            ignored_paths.clone(),
+            &ignore_globs,
             std::path::Path::new(&test_path),
             &mut |file| {
                 actual_result.push(file);
@@ -130,7 +125,12 @@ mod tests {
         let (test_path, files) = generate_test_files(String::from("tmp-formatter"))
             .expect("Couldn't create test files!");
 
-        let mut actual_result = grab_directory_and_files(test_path.clone()).unwrap();
+        let mut actual_result = grab_directory_and_files(
+            test_path.clone(),
+            vec![String::from("target/debug")],
+            utils::FileFilter::default(),
+        )
+        .unwrap();
 
         let mut expected_result = vec![
             Files {
@@ -180,7 +180,7 @@ mod tests {
         }
         let actual_result = utils::get_list_differences(updated_files, files.clone()).unwrap();
 
-        assert_eq!(actual_result, expected_result);
+        assert_eq!(actual_result.added, expected_result);
 
         // Check timestamp changes are detected
         let mut file_date_changed = files[1].clone();
@@ -189,7 +189,7 @@ mod tests {
         let expected_result = vec![updated_files[1].clone()];
         let actual_result = utils::get_list_differences(updated_files, files.clone()).unwrap();
 
-        assert_eq!(actual_result, expected_result);
+        assert_eq!(actual_result.modified, expected_result);
     }
 
     #[test]
@@ -201,7 +201,15 @@ mod tests {
         let (sender, receiver) = std::sync::mpsc::channel::<WatcherEvent>();
 
         let thread_path_clone = test_path.clone();
-        let worker = std::thread::spawn(move || utils::dir_watcher(thread_path_clone, sender));
+        let worker = std::thread::spawn(move || {
+            utils::dir_watcher(
+                thread_path_clone,
+                sender,
+                WatchMode::default(),
+                Vec::new(),
+                Duration::from_millis(100),
+            )
+        });
 
         // Wait a moment, to ensure that files have been collected first
         std::thread::sleep(Duration::from_millis(500));
@@ -232,40 +240,6 @@ mod tests {
         assert!(worker.join().is_ok());
     }
 
-    #[test]
-    fn watch_rs_get_exe_from_dir() {
-        // Test setup
-        let (test_path, _files) = generate_test_files(String::from("tmp-exe-dir-finder"))
-            .expect("Couldn't create test files!");
-
-        // Check that an executable name is returned from a valid build directory
-        block_on(async {
-            let exe_name = get_executable_from_dir(test_path.clone()).await.unwrap();
-
-            assert_eq!(exe_name, String::from("test_exe_0.exe"));
-        });
-
-        // Clear files before assertion, in case assertion
-        cleanup_test_files(test_path).expect("Couldn't clean up files!");
-    }
-
-    #[test]
-    fn watch_rs_test_name() {
-        // Test setup
-        let (test_path, _files) = generate_test_files(String::from("tmp-pid-finder"))
-            .expect("Couldn't create test files!");
-
-        // Check that a PID is returned when supplied a valid running process name
-        block_on(async {
-            let pid = get_executable_id(String::from("cargo.exe")).await.unwrap();
-
-            assert_ne!(pid, sysinfo::Pid::from(0));
-        });
-
-        // Clear files before assertion, in case assertion
-        cleanup_test_files(test_path).expect("Couldn't clean up files!");
-    }
-
     // Test Example
     // #[test]
     // fn watch_rs_test_name() {